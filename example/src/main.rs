@@ -1,6 +1,7 @@
-use raycast_rust_macros::raycast;
+use raycast_rust_macros::{raycast, raycast_type};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use futures::Stream;
 
 #[raycast]
 fn noop() {
@@ -33,18 +34,46 @@ fn optionals(value: Option<String>) -> Option<String> {
 }
 
 #[raycast]
-fn pick_color(name: String) -> Result<Color, String> {
+fn pick_color(name: String) -> Result<Color, UnknownColor> {
     match name.as_str() {
         "red" => Ok(Color { red: 1.0, green: 0.0, blue: 0.0 }),
         "green" => Ok(Color { red: 0.0, green: 1.0, blue: 0.0 }),
         "blue" => Ok(Color { red: 0.0, green: 0.0, blue: 1.0 }),
-        _ => Err(format!("{name} is not a supported color")),
+        _ => Err(UnknownColor(name)),
     }
 }
 
+#[raycast_type]
 #[derive(Deserialize, Serialize)]
 struct Color {
     red: f32,
     green: f32,
     blue: f32,
 }
+
+/// The error returned by [`pick_color`] for an unrecognized name, demonstrating
+/// [`raycast_rust_runtime::RaycastFailure`]: the TypeScript caller gets a
+/// `{code, message, data}` object instead of a bare string, and can branch on
+/// `code` or read `data.name` instead of string-matching the message.
+struct UnknownColor(String);
+
+impl std::fmt::Display for UnknownColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is not a supported color", self.0)
+    }
+}
+
+impl raycast_rust_runtime::RaycastFailure for UnknownColor {
+    fn code(&self) -> i32 {
+        1
+    }
+
+    fn data(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({ "name": self.0 }))
+    }
+}
+
+#[raycast(stream)]
+fn countdown(from: u32) -> impl Stream<Item = u32> {
+    futures::stream::iter((0..=from).rev())
+}
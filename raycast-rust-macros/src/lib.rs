@@ -1,6 +1,6 @@
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, ItemFn, ReturnType, FnArg, Pat, Type};
+use quote::{quote, ToTokens};
+use syn::{parse_macro_input, Fields, GenericArgument, ItemFn, ItemStruct, PathArguments, ReturnType, FnArg, Pat, Type};
 use std::sync::atomic::{AtomicBool, Ordering};
 
 // Global flag to track if main has been generated
@@ -15,16 +15,74 @@ static MAIN_GENERATED: AtomicBool = AtomicBool::new(false);
 ///     format!("Hello {}{name}!", if is_formal { "Mr/Ms " } else { "" })
 /// }
 /// ```
+///
+/// `#[raycast(stream)]` marks a function that returns `impl Stream<Item = T>`
+/// (or `impl Stream<Item = Result<T, E>>`) instead of a single value; each
+/// yielded item is written out as its own NDJSON line as it arrives.
 #[proc_macro_attribute]
-pub fn raycast(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn raycast(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let is_stream = attr.to_string().trim() == "stream";
     let input_fn = parse_macro_input!(item as ItemFn);
 
-    let expanded = expand_raycast_function(input_fn);
+    let expanded = expand_raycast_function(input_fn, is_stream);
+
+    TokenStream::from(expanded)
+}
+
+/// Attribute macro that records a struct's field layout for `--emit-types`,
+/// so a `#[derive(Serialize)]` struct used as a `#[raycast]` parameter or
+/// return type expands into a real TypeScript `interface` instead of an
+/// opaque `unknown` stub.
+///
+/// Usage:
+/// ```rust
+/// #[raycast_type]
+/// #[derive(Serialize)]
+/// struct Color {
+///     red: f32,
+///     green: f32,
+///     blue: f32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn raycast_type(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input_struct = parse_macro_input!(item as ItemStruct);
+
+    let expanded = expand_raycast_type(input_struct);
 
     TokenStream::from(expanded)
 }
 
-fn expand_raycast_function(input_fn: ItemFn) -> proc_macro2::TokenStream {
+fn expand_raycast_type(input_struct: ItemStruct) -> proc_macro2::TokenStream {
+    let struct_name_str = input_struct.ident.to_string();
+
+    let Fields::Named(fields) = &input_struct.fields else {
+        panic!("#[raycast_type] only supports structs with named fields");
+    };
+
+    let field_metadata = fields.named.iter().map(|field| {
+        let name_str = field
+            .ident
+            .as_ref()
+            .expect("named field always has an ident")
+            .to_string();
+        let ty_str = type_to_source_string(&field.ty);
+        quote! { (#name_str, #ty_str) }
+    });
+
+    quote! {
+        #input_struct
+
+        raycast_rust_runtime::inventory::submit! {
+            raycast_rust_runtime::RaycastTypeInfo {
+                name: #struct_name_str,
+                fields: &[#(#field_metadata),*],
+            }
+        }
+    }
+}
+
+fn expand_raycast_function(input_fn: ItemFn, is_stream: bool) -> proc_macro2::TokenStream {
     let fn_name = &input_fn.sig.ident;
     let fn_name_str = fn_name.to_string();
     let fn_vis = &input_fn.vis;
@@ -33,7 +91,7 @@ fn expand_raycast_function(input_fn: ItemFn) -> proc_macro2::TokenStream {
     let fn_sig = &input_fn.sig;
 
     // Validate function signature
-    validate_function_signature(&input_fn.sig);
+    validate_function_signature(&input_fn.sig, is_stream);
 
     // Extract parameter information
     let param_names: Vec<_> = input_fn.sig.inputs.iter()
@@ -69,8 +127,49 @@ fn expand_raycast_function(input_fn: ItemFn) -> proc_macro2::TokenStream {
         ReturnType::Default => false,
     };
 
+    // For a stream, the "item" is what gets serialized per yielded value
+    // (unwrapped from `Result<T, E>` if the stream yields results), rather
+    // than the function's own return value.
+    let stream_item_str = if is_stream {
+        match &input_fn.sig.output {
+            ReturnType::Type(_, ty) => Some(
+                extract_stream_item_type(ty)
+                    .unwrap_or_else(|| panic!("#[raycast(stream)] requires a return type of `impl Stream<Item = T>`")),
+            ),
+            ReturnType::Default => panic!("#[raycast(stream)] requires a return type of `impl Stream<Item = T>`"),
+        }
+    } else {
+        None
+    };
+    let stream_returns_result = stream_item_str.as_deref().is_some_and(|s| s.starts_with("Result<"));
+
+    // The type text recorded for TypeScript codegen: for `Result<T, E>` this
+    // is `T`, since a JS caller only ever sees the success value (errors
+    // reject the promise/stream instead).
+    let returns_str = if let Some(item_str) = &stream_item_str {
+        if stream_returns_result {
+            first_generic_arg(item_str).unwrap_or_else(|| item_str.clone())
+        } else {
+            item_str.clone()
+        }
+    } else {
+        match &input_fn.sig.output {
+            ReturnType::Type(_, ty) => type_to_source_string(result_ok_type(ty).unwrap_or(ty.as_ref())),
+            ReturnType::Default => "()".to_string(),
+        }
+    };
+
     // Generate the registry entry
-    let registry_entry = generate_registry_entry(&fn_name_str, &param_names, &param_types, is_async, returns_result);
+    let registry_entry = generate_registry_entry(
+        &fn_name_str,
+        &param_names,
+        &param_types,
+        is_async,
+        returns_result,
+        &returns_str,
+        is_stream,
+        stream_returns_result,
+    );
 
     // Check if we should generate main function
     let should_generate_main = !MAIN_GENERATED.swap(true, Ordering::SeqCst);
@@ -99,7 +198,7 @@ fn expand_raycast_function(input_fn: ItemFn) -> proc_macro2::TokenStream {
     }
 }
 
-fn validate_function_signature(sig: &syn::Signature) {
+fn validate_function_signature(sig: &syn::Signature, is_stream: bool) {
     // Check for unsupported features
     if sig.variadic.is_some() {
         panic!("Variadic functions are not supported with #[raycast]");
@@ -111,24 +210,62 @@ fn validate_function_signature(sig: &syn::Signature) {
             panic!("Methods with self parameters are not supported with #[raycast]. Use free functions instead.");
         }
     }
+
+    if is_stream && sig.asyncness.is_some() {
+        panic!(
+            "#[raycast(stream)] functions must not be `async fn` \u{2014} return `impl Stream<Item = T>` \
+             directly and do any async work inside the stream itself."
+        );
+    }
 }
 
-fn generate_param_parsing(param_names: &[&syn::Ident], param_types: &[&Type]) -> proc_macro2::TokenStream {
+fn generate_param_parsing(
+    param_names: &[&syn::Ident],
+    param_types: &[&Type],
+    function_name_expr: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
     let param_count = param_names.len();
 
+    // Positional params must supply exactly the declared parameter count;
+    // named params are looked up by key below, so no count check applies.
+    let count_check = quote! {
+        if let raycast_rust_runtime::Params::Positional(positional_args) = &params {
+            if positional_args.len() != #param_count {
+                return Err(raycast_rust_runtime::RaycastError::ArgumentCountMismatch {
+                    function: #function_name_expr,
+                    expected: #param_count,
+                    actual: positional_args.len(),
+                });
+            }
+        }
+    };
+
     let parsing_code = param_names.iter().zip(param_types.iter()).enumerate().map(|(i, (name, ty))| {
         let json_var = syn::Ident::new(&format!("{}_json", name), name.span());
         quote! {
-            let #json_var = args.get(#i)
-                .ok_or_else(|| raycast_rust_runtime::RaycastError::MissingArgument {
-                    function: _function_name.to_string(),
-                    parameter: stringify!(#name).to_string(),
-                    position: #i,
-                })?
-                .clone();
+            let #json_var = match &params {
+                raycast_rust_runtime::Params::Positional(positional_args) => {
+                    positional_args.get(#i)
+                        .cloned()
+                        .ok_or_else(|| raycast_rust_runtime::RaycastError::MissingArgument {
+                            function: #function_name_expr,
+                            parameter: stringify!(#name).to_string(),
+                            position: #i,
+                        })?
+                }
+                raycast_rust_runtime::Params::Named(named_args) => {
+                    named_args.get(stringify!(#name))
+                        .cloned()
+                        .ok_or_else(|| raycast_rust_runtime::RaycastError::MissingArgument {
+                            function: #function_name_expr,
+                            parameter: stringify!(#name).to_string(),
+                            position: #i,
+                        })?
+                }
+            };
             let #name: #ty = serde_json::from_value(#json_var)
                 .map_err(|e| raycast_rust_runtime::RaycastError::DecodingError {
-                    function: _function_name.to_string(),
+                    function: #function_name_expr,
                     parameter: stringify!(#name).to_string(),
                     position: #i,
                     error: e.to_string(),
@@ -137,22 +274,29 @@ fn generate_param_parsing(param_names: &[&syn::Ident], param_types: &[&Type]) ->
     });
 
     quote! {
-        if args.len() != #param_count {
-            return Err(raycast_rust_runtime::RaycastError::ArgumentCountMismatch {
-                function: _function_name.to_string(),
-                expected: #param_count,
-                actual: args.len(),
-            });
-        }
-
+        #count_check
         #(#parsing_code)*
     }
 }
 
 
-fn generate_registry_entry(fn_name_str: &str, param_names: &[&syn::Ident], param_types: &[&Type], is_async: bool, returns_result: bool) -> proc_macro2::TokenStream {
+fn generate_registry_entry(
+    fn_name_str: &str,
+    param_names: &[&syn::Ident],
+    param_types: &[&Type],
+    is_async: bool,
+    returns_result: bool,
+    returns_str: &str,
+    is_stream: bool,
+    stream_returns_result: bool,
+) -> proc_macro2::TokenStream {
     let fn_ident = syn::Ident::new(&fn_name_str, proc_macro2::Span::call_site());
-    let param_parsing = generate_param_parsing(param_names, param_types);
+
+    let param_metadata = param_names.iter().zip(param_types.iter()).map(|(name, ty)| {
+        let name_str = name.to_string();
+        let ty_str = type_to_source_string(ty);
+        quote! { (#name_str, #ty_str) }
+    });
 
     let function_call = quote! { #fn_ident(#(#param_names),*) };
 
@@ -166,11 +310,31 @@ fn generate_registry_entry(fn_name_str: &str, param_names: &[&syn::Ident], param
         }
     };
 
-    let execute_fn = if is_async {
+    // The boxed/async entry point. Every function gets one, so `execute` can
+    // stay a plain `fn` (not `Option`) on `RaycastFunction`; non-async
+    // functions additionally get `execute_sync` below, which
+    // `RaycastExecutor::execute` prefers.
+    let boxed_param_parsing =
+        generate_param_parsing(param_names, param_types, &quote! { _function_name.to_string() });
+
+    let execute_fn = if is_stream {
+        // Streaming functions are dispatched through `execute_stream`
+        // instead; calling them through the ordinary one-shot path is a
+        // programmer error, not a runtime condition to special-case further.
+        quote! {
+            |_function_name: String, _params: raycast_rust_runtime::Params| {
+                Box::pin(async move {
+                    Err(raycast_rust_runtime::RaycastError::ExecutionError {
+                        error: format!("'{}' is a streaming function; call it via RaycastExecutor::execute_stream", _function_name),
+                    })
+                })
+            }
+        }
+    } else if is_async {
         quote! {
-            |_function_name: String, args: Vec<serde_json::Value>| {
+            |_function_name: String, params: raycast_rust_runtime::Params| {
                 Box::pin(async move {
-                    #param_parsing
+                    #boxed_param_parsing
                     let result = #function_call.await;
                     #result_handling
                 })
@@ -178,9 +342,9 @@ fn generate_registry_entry(fn_name_str: &str, param_names: &[&syn::Ident], param
         }
     } else {
         quote! {
-            |_function_name: String, args: Vec<serde_json::Value>| {
+            |_function_name: String, params: raycast_rust_runtime::Params| {
                 Box::pin(async move {
-                    #param_parsing
+                    #boxed_param_parsing
                     let result = #function_call;
                     #result_handling
                 })
@@ -188,11 +352,64 @@ fn generate_registry_entry(fn_name_str: &str, param_names: &[&syn::Ident], param
         }
     };
 
+    // Non-async, non-streaming functions also get a synchronous fast path:
+    // no future allocation, no `Box::pin`, driven straight to a `Result`.
+    let execute_sync = if is_async || is_stream {
+        quote! { None }
+    } else {
+        let sync_param_parsing =
+            generate_param_parsing(param_names, param_types, &quote! { #fn_name_str.to_string() });
+        quote! {
+            Some((|params: raycast_rust_runtime::Params| -> Result<serde_json::Value, raycast_rust_runtime::RaycastError> {
+                #sync_param_parsing
+                let result = #function_call;
+                #result_handling
+            }) as fn(raycast_rust_runtime::Params) -> Result<serde_json::Value, raycast_rust_runtime::RaycastError>)
+        }
+    };
+
+    // Streaming functions get a dispatch entry that builds the stream (doing
+    // parameter parsing eagerly) and maps each yielded item through the same
+    // serialization helpers `execute`/`execute_sync` use for a single value.
+    let execute_stream = if is_stream {
+        let stream_param_parsing =
+            generate_param_parsing(param_names, param_types, &quote! { #fn_name_str.to_string() });
+        let item_handling = if stream_returns_result {
+            quote! { raycast_rust_runtime::serialize_result_to_json(item) }
+        } else {
+            quote! { raycast_rust_runtime::serialize_to_json(item) }
+        };
+
+        quote! {
+            Some((|params: raycast_rust_runtime::Params| -> std::pin::Pin<Box<dyn raycast_rust_runtime::futures::Stream<Item = Result<serde_json::Value, raycast_rust_runtime::RaycastError>> + Send>> {
+                let parsed = (|| -> Result<_, raycast_rust_runtime::RaycastError> {
+                    #stream_param_parsing
+                    Ok((#(#param_names),*))
+                })();
+
+                match parsed {
+                    Ok((#(#param_names),*)) => {
+                        let stream = #function_call;
+                        Box::pin(raycast_rust_runtime::futures::StreamExt::map(stream, |item| #item_handling))
+                    }
+                    Err(error) => Box::pin(raycast_rust_runtime::futures::stream::once(async move { Err(error) })),
+                }
+            }) as fn(raycast_rust_runtime::Params) -> std::pin::Pin<Box<dyn raycast_rust_runtime::futures::Stream<Item = Result<serde_json::Value, raycast_rust_runtime::RaycastError>> + Send>>)
+        }
+    } else {
+        quote! { None }
+    };
+
     quote! {
         raycast_rust_runtime::inventory::submit! {
             raycast_rust_runtime::RaycastFunction {
                 name: #fn_name_str,
+                params: &[#(#param_metadata),*],
+                returns: #returns_str,
+                is_stream: #is_stream,
                 execute: #execute_fn,
+                execute_sync: #execute_sync,
+                execute_stream: #execute_stream,
             }
         }
     }
@@ -206,3 +423,81 @@ fn is_result_type(ty: &Type) -> bool {
     }
     false
 }
+
+/// If `ty` is `Result<T, E>`, return `T`; otherwise `None`.
+fn result_ok_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+/// Render a type's source text (e.g. `Vec<String>`) with whitespace
+/// collapsed, for storage as a `&'static str` on `RaycastFunction`.
+fn type_to_source_string(ty: &Type) -> String {
+    ty.to_token_stream().to_string().split_whitespace().collect()
+}
+
+/// If `ty` is `impl Stream<Item = T>` (with any other bounds alongside it,
+/// e.g. `+ Send` or `+ Unpin`), return `T`'s source text with whitespace
+/// collapsed. Parses the actual `impl Trait` bound AST rather than the
+/// rendered token text, so trailing bounds after `Stream<Item = T>` don't get
+/// swept into the extracted type.
+fn extract_stream_item_type(ty: &Type) -> Option<String> {
+    let Type::ImplTrait(impl_trait) = ty else {
+        return None;
+    };
+
+    for bound in &impl_trait.bounds {
+        let syn::TypeParamBound::Trait(trait_bound) = bound else {
+            continue;
+        };
+        let segment = trait_bound.path.segments.last()?;
+        if segment.ident != "Stream" {
+            continue;
+        }
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            continue;
+        };
+        for arg in &args.args {
+            if let GenericArgument::AssocType(assoc) = arg {
+                if assoc.ident == "Item" {
+                    return Some(type_to_source_string(&assoc.ty));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Given source text like `Result<T, E>`, return `T`'s source text.
+fn first_generic_arg(s: &str) -> Option<String> {
+    let start = s.find('<')? + 1;
+    let end = s.rfind('>')?;
+    if end <= start {
+        return None;
+    }
+
+    let inner = &s[start..end];
+    let mut depth = 0i32;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => return Some(inner[..i].to_string()),
+            _ => {}
+        }
+    }
+    Some(inner.to_string())
+}
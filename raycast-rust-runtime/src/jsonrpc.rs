@@ -0,0 +1,247 @@
+use std::io::{BufRead, Stdout, Write};
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{Params, RaycastError, RaycastExecutor};
+
+/// A single JSON-RPC 2.0 request object.
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    method: String,
+    #[serde(default)]
+    params: Params,
+    /// `None` means the `id` field was absent: a notification. `Some(Value::Null)`
+    /// means it was present and explicitly `null`: a request that must still be
+    /// answered (with `id: null`). `#[serde(default)]` alone can't tell these
+    /// apart, since serde's `Option<T>` deserializes a JSON `null` to `None`
+    /// regardless of whether the field was present; `deserialize_id` below is
+    /// only invoked when the field is present, so the distinction survives.
+    #[serde(default, deserialize_with = "deserialize_id")]
+    id: Option<Value>,
+}
+
+fn deserialize_id<'de, D>(deserializer: D) -> Result<Option<Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Value::deserialize(deserializer).map(Some)
+}
+
+/// A single JSON-RPC 2.0 response object.
+#[derive(Debug, Serialize)]
+struct JsonRpcResponse {
+    jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<JsonRpcErrorObject>,
+    id: Value,
+}
+
+/// A JSON-RPC 2.0 error object.
+#[derive(Debug, Serialize)]
+struct JsonRpcErrorObject {
+    code: i32,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+impl From<&RaycastError> for JsonRpcErrorObject {
+    fn from(err: &RaycastError) -> Self {
+        let (code, data) = err.code_and_data();
+
+        JsonRpcErrorObject {
+            code,
+            message: err.to_string(),
+            data,
+        }
+    }
+}
+
+impl RaycastExecutor {
+    /// Run a persistent JSON-RPC 2.0 server over stdin/stdout.
+    ///
+    /// Reads newline-delimited JSON-RPC requests from stdin (a top-level JSON
+    /// array is treated as a batch) and dispatches each `method`/`params`
+    /// pair through [`RaycastExecutor::execute`]. A single request gets one
+    /// response object per line; a batch gets its responses collected into a
+    /// single JSON array on one line, per the JSON-RPC 2.0 spec. Requests
+    /// with no `id` are notifications: they still run, but produce no
+    /// response, and a batch of only notifications produces no line at all.
+    /// This lets a caller spawn the binary once and issue many calls over its
+    /// lifetime instead of paying process-startup cost per call.
+    pub async fn serve_stdio() -> Result<(), RaycastError> {
+        let stdin = std::io::stdin();
+        let mut stdout = std::io::stdout();
+
+        for line in stdin.lock().lines() {
+            let line = line.map_err(|e| RaycastError::JsonError {
+                error: format!("Failed to read from stdin: {}", e),
+            })?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let value: Value = match serde_json::from_str(&line) {
+                Ok(value) => value,
+                Err(e) => {
+                    Self::write_response(
+                        &mut stdout,
+                        &JsonRpcResponse {
+                            jsonrpc: "2.0",
+                            result: None,
+                            error: Some(JsonRpcErrorObject {
+                                code: -32700,
+                                message: format!("Parse error: {}", e),
+                                data: None,
+                            }),
+                            id: Value::Null,
+                        },
+                    )?;
+                    continue;
+                }
+            };
+
+            let is_batch = matches!(value, Value::Array(_));
+
+            let responses = match value {
+                Value::Array(requests) => {
+                    let mut responses = Vec::with_capacity(requests.len());
+                    for request in requests {
+                        responses.extend(Self::handle_one(request, true).await);
+                    }
+                    responses
+                }
+                request => Self::handle_one(request, false).await.into_iter().collect(),
+            };
+
+            if is_batch {
+                // JSON-RPC 2.0 requires a batch reply to be a single Array,
+                // not one line per response; an all-notification batch gets
+                // no reply at all.
+                if !responses.is_empty() {
+                    Self::write_batch_response(&mut stdout, &responses)?;
+                }
+            } else {
+                for response in &responses {
+                    Self::write_response(&mut stdout, response)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Dispatch a single decoded JSON-RPC request, returning `None` for notifications.
+    ///
+    /// `in_batch` rejects streaming requests instead of driving them: a batch
+    /// reply is one JSON array written after every request in the batch has
+    /// been handled, but a stream writes its `item`/`done` NDJSON lines to
+    /// stdout as they arrive, which would interleave ahead of that array and
+    /// break the "one batch = one line" framing. Call a streaming method on
+    /// its own, outside a batch, instead. A streaming notification writes no
+    /// output, so it is driven to completion whether or not it is in a batch.
+    async fn handle_one(value: Value, in_batch: bool) -> Option<JsonRpcResponse> {
+        let request: JsonRpcRequest = match serde_json::from_value(value) {
+            Ok(request) => request,
+            Err(e) => {
+                return Some(JsonRpcResponse {
+                    jsonrpc: "2.0",
+                    result: None,
+                    error: Some(JsonRpcErrorObject {
+                        code: -32600,
+                        message: format!("Invalid request: {}", e),
+                        data: None,
+                    }),
+                    id: Value::Null,
+                })
+            }
+        };
+
+        let id = request.id.clone();
+
+        if Self::is_stream_function(&request.method) {
+            let Some(id) = id else {
+                // notification: drive the stream to completion, but write none of its output
+                if let Ok(mut stream) = Self::execute_stream(&request.method, request.params) {
+                    while stream.next().await.is_some() {}
+                }
+                return None;
+            };
+
+            if in_batch {
+                return Some(JsonRpcResponse {
+                    jsonrpc: "2.0",
+                    result: None,
+                    error: Some(JsonRpcErrorObject {
+                        code: -32600,
+                        message: format!(
+                            "'{}' is a streaming function and cannot be called inside a batch",
+                            request.method
+                        ),
+                        data: None,
+                    }),
+                    id,
+                });
+            }
+
+            return match Self::execute_stream(&request.method, request.params) {
+                Ok(stream) => {
+                    let _ = Self::drive_stream_to_stdout(stream, Some(id)).await;
+                    None
+                }
+                Err(e) => Some(JsonRpcResponse {
+                    jsonrpc: "2.0",
+                    result: None,
+                    error: Some(JsonRpcErrorObject::from(&e)),
+                    id,
+                }),
+            };
+        }
+
+        let result = Self::execute(&request.method, request.params).await;
+
+        let id = id?; // notification: run, but don't respond
+
+        Some(match result {
+            Ok(value) => JsonRpcResponse {
+                jsonrpc: "2.0",
+                result: Some(value),
+                error: None,
+                id,
+            },
+            Err(e) => JsonRpcResponse {
+                jsonrpc: "2.0",
+                result: None,
+                error: Some(JsonRpcErrorObject::from(&e)),
+                id,
+            },
+        })
+    }
+
+    fn write_response(stdout: &mut Stdout, response: &JsonRpcResponse) -> Result<(), RaycastError> {
+        let line = serde_json::to_string(response).map_err(|e| RaycastError::JsonError {
+            error: format!("Failed to serialize response: {}", e),
+        })?;
+        writeln!(stdout, "{}", line).map_err(|e| RaycastError::JsonError {
+            error: format!("Failed to write to stdout: {}", e),
+        })?;
+        Ok(())
+    }
+
+    /// Write a batch's responses as a single JSON array on one line, per
+    /// JSON-RPC 2.0's batch reply format.
+    fn write_batch_response(stdout: &mut Stdout, responses: &[JsonRpcResponse]) -> Result<(), RaycastError> {
+        let line = serde_json::to_string(responses).map_err(|e| RaycastError::JsonError {
+            error: format!("Failed to serialize response: {}", e),
+        })?;
+        writeln!(stdout, "{}", line).map_err(|e| RaycastError::JsonError {
+            error: format!("Failed to write to stdout: {}", e),
+        })?;
+        Ok(())
+    }
+}
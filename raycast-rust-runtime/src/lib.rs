@@ -2,8 +2,13 @@ use std::pin::Pin;
 use std::future::Future;
 use serde_json::Value;
 
-// Re-export inventory for macro use
+mod jsonrpc;
+mod streaming;
+mod typegen;
+
+// Re-export inventory and futures for macro use
 pub use inventory;
+pub use futures;
 
 /// Errors that can occur during function execution
 #[derive(Debug, thiserror::Error)]
@@ -44,22 +49,145 @@ pub enum RaycastError {
     JsonError {
         error: String,
     },
+
+    /// A [`RaycastFailure`]-implementing error returned by a `#[raycast]`
+    /// function, carrying the caller-supplied `code` and optional `data`
+    /// through to the JSON-RPC/CLI error surface instead of being flattened
+    /// to a bare string.
+    #[error("Function execution failed: {error}")]
+    Failure {
+        error: String,
+        code: i32,
+        data: Option<Value>,
+    },
+}
+
+impl RaycastError {
+    /// The `(code, data)` pair to surface to a caller: the JSON-RPC reserved
+    /// range for everything but [`RaycastError::Failure`], which carries its
+    /// own caller-supplied code and data through unchanged.
+    pub fn code_and_data(&self) -> (i32, Option<Value>) {
+        match self {
+            RaycastError::FunctionNotFound { .. } => (-32601, None),
+            RaycastError::MissingArgument { .. }
+            | RaycastError::ArgumentCountMismatch { .. }
+            | RaycastError::DecodingError { .. } => (-32602, None),
+            RaycastError::JsonError { .. } => (-32700, None),
+            RaycastError::ExecutionError { .. } => (-32000, None),
+            RaycastError::Failure { code, data, .. } => (*code, data.clone()),
+        }
+    }
+}
+
+/// Implemented by a `#[raycast]` function's error type to attach a
+/// structured error code and optional machine-readable payload. Surfaced as
+/// `{code, message, data}` instead of a bare string, so a TypeScript caller
+/// can branch on `code` rather than string-matching the message.
+pub trait RaycastFailure: std::fmt::Display {
+    /// A caller-defined error code. The `String` impl below returns -32000,
+    /// the same code [`RaycastError::ExecutionError`] maps to; other
+    /// implementations supply their own application-specific codes.
+    fn code(&self) -> i32;
+
+    /// An optional machine-readable payload describing the failure.
+    fn data(&self) -> Option<Value> {
+        None
+    }
+}
+
+impl RaycastFailure for String {
+    fn code(&self) -> i32 {
+        -32000
+    }
+}
+
+/// Arguments passed to a registered function: positional (a JSON array) or
+/// named (a JSON object), mirroring the two shapes JSON-RPC 2.0 allows for
+/// `params`.
+#[derive(Debug, Clone)]
+pub enum Params {
+    Positional(Vec<Value>),
+    Named(serde_json::Map<String, Value>),
+}
+
+impl Params {
+    /// Build `Params` from an already-parsed JSON value: a top-level array
+    /// becomes [`Params::Positional`], an object becomes [`Params::Named`],
+    /// and `null` becomes an empty positional list.
+    pub fn from_value(value: Value) -> Result<Self, RaycastError> {
+        match value {
+            Value::Array(items) => Ok(Params::Positional(items)),
+            Value::Object(map) => Ok(Params::Named(map)),
+            Value::Null => Ok(Params::Positional(Vec::new())),
+            other => Err(RaycastError::JsonError {
+                error: format!("Expected a JSON array or object for arguments, got {}", other),
+            }),
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params::Positional(Vec::new())
+    }
+}
+
+impl From<Vec<Value>> for Params {
+    fn from(args: Vec<Value>) -> Self {
+        Params::Positional(args)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Params {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        Params::from_value(value).map_err(serde::de::Error::custom)
+    }
 }
 
 /// A registered Raycast function
 pub struct RaycastFunction {
     pub name: &'static str,
-    pub execute: fn(String, Vec<Value>) -> Pin<Box<dyn Future<Output = Result<Value, RaycastError>> + Send + 'static>>,
+    /// `(parameter name, Rust type source text)` for each declared parameter, in order.
+    pub params: &'static [(&'static str, &'static str)],
+    /// Rust type source text of the function's return value: the stream's
+    /// item type for streaming functions, otherwise the `Ok` type for
+    /// `Result`-returning functions.
+    pub returns: &'static str,
+    /// Whether this function was declared `#[raycast(stream)]`.
+    pub is_stream: bool,
+    pub execute: fn(String, Params) -> Pin<Box<dyn Future<Output = Result<Value, RaycastError>> + Send + 'static>>,
+    /// A synchronous fast path for non-`async`, non-streaming functions: no
+    /// future allocation, no `Box::pin`. `None` otherwise.
+    pub execute_sync: Option<fn(Params) -> Result<Value, RaycastError>>,
+    /// Present only for `#[raycast(stream)]` functions: builds the stream of
+    /// results to drive to completion via [`RaycastExecutor::execute_stream`].
+    pub execute_stream:
+        Option<fn(Params) -> Pin<Box<dyn futures::Stream<Item = Result<Value, RaycastError>> + Send>>>,
 }
 
 inventory::collect!(RaycastFunction);
 
+/// A struct's field layout, recorded by `#[raycast_type]` so `--emit-types`
+/// can expand a `#[derive(Serialize)]` struct referenced by a `#[raycast]`
+/// function into a real TypeScript `interface` instead of an opaque stub.
+pub struct RaycastTypeInfo {
+    pub name: &'static str,
+    /// `(field name, Rust type source text)` for each named field, in order.
+    pub fields: &'static [(&'static str, &'static str)],
+}
+
+inventory::collect!(RaycastTypeInfo);
+
 /// Main executor for Raycast functions
 pub struct RaycastExecutor;
 
 impl RaycastExecutor {
     /// Execute a function by name with the given arguments
-    pub async fn execute(function_name: &str, args: Vec<Value>) -> Result<Value, RaycastError> {
+    pub async fn execute(function_name: &str, params: impl Into<Params>) -> Result<Value, RaycastError> {
         // Find the function in the registry
         let function = inventory::iter::<RaycastFunction>()
             .find(|f| f.name == function_name)
@@ -67,8 +195,15 @@ impl RaycastExecutor {
                 function: function_name.to_string(),
             })?;
 
-        // Execute the function
-        (function.execute)(function_name.to_string(), args).await
+        let params = params.into();
+
+        // Prefer the zero-allocation synchronous path when the function isn't
+        // genuinely async, and only await the boxed future otherwise.
+        if let Some(execute_sync) = function.execute_sync {
+            return execute_sync(params);
+        }
+
+        (function.execute)(function_name.to_string(), params).await
     }
 
     /// Run the main CLI loop
@@ -77,9 +212,20 @@ impl RaycastExecutor {
 
         if args.len() < 2 {
             eprintln!("Usage: {} <function_name> [args...]", args[0]);
+            eprintln!("       {} --serve", args[0]);
+            eprintln!("       {} --emit-types", args[0]);
             std::process::exit(1);
         }
 
+        if args[1] == "--serve" {
+            return Self::serve_stdio().await;
+        }
+
+        if args[1] == "--emit-types" {
+            print!("{}", Self::emit_dts());
+            return Ok(());
+        }
+
         let function_name = &args[1];
 
         // Read JSON arguments from stdin
@@ -91,27 +237,55 @@ impl RaycastExecutor {
             }
         })?;
 
-        // Parse JSON arguments
-        let json_args: Vec<Value> = if input.trim().is_empty() {
-            vec![]
+        // Parse JSON arguments (either a positional array or a named object)
+        let params = if input.trim().is_empty() {
+            Params::Positional(vec![])
         } else {
-            serde_json::from_str(&input).map_err(|e| RaycastError::JsonError {
+            let value: Value = serde_json::from_str(&input).map_err(|e| RaycastError::JsonError {
                 error: format!("Failed to parse JSON arguments: {}", e),
-            })?
+            })?;
+            Params::from_value(value)?
         };
 
+        if Self::is_stream_function(function_name) {
+            return match Self::execute_stream(function_name, params) {
+                Ok(stream) => Self::drive_stream_to_stdout(stream, None).await,
+                Err(e) => {
+                    Self::print_cli_error(&e);
+                    std::process::exit(1);
+                }
+            };
+        }
+
         // Execute the function
-        match Self::execute(function_name, json_args).await {
+        match Self::execute(function_name, params).await {
             Ok(result) => {
                 println!("{}", serde_json::to_string(&result).unwrap());
                 Ok(())
             }
             Err(e) => {
-                eprintln!("Error: {}", e);
+                Self::print_cli_error(&e);
                 std::process::exit(1);
             }
         }
     }
+
+    /// Print an error to stderr, as a `{code, message, data}` JSON object for
+    /// [`RaycastError::Failure`] so the caller can parse out the structured
+    /// fields, or as a plain message otherwise.
+    fn print_cli_error(error: &RaycastError) {
+        if let RaycastError::Failure { .. } = error {
+            let (code, data) = error.code_and_data();
+            let error_object = serde_json::json!({
+                "code": code,
+                "message": error.to_string(),
+                "data": data,
+            });
+            eprintln!("{}", serde_json::to_string(&error_object).unwrap());
+        } else {
+            eprintln!("Error: {}", error);
+        }
+    }
 }
 
 /// Helper functions for converting results to JSON values
@@ -124,12 +298,14 @@ pub fn serialize_to_json<T: serde::Serialize>(value: T) -> Result<Value, Raycast
 pub fn serialize_result_to_json<T, E>(result: Result<T, E>) -> Result<Value, RaycastError>
 where
     T: serde::Serialize,
-    E: std::fmt::Display,
+    E: RaycastFailure,
 {
     match result {
         Ok(value) => serialize_to_json(value),
-        Err(e) => Err(RaycastError::ExecutionError {
+        Err(e) => Err(RaycastError::Failure {
             error: e.to_string(),
+            code: e.code(),
+            data: e.data(),
         }),
     }
 }
@@ -0,0 +1,122 @@
+use std::io::Write;
+use std::pin::Pin;
+
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Params, RaycastError, RaycastExecutor, RaycastFunction};
+
+/// One NDJSON line written while a stream is in progress.
+#[derive(Serialize)]
+struct StreamItemLine {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<Value>,
+    item: Value,
+}
+
+/// One NDJSON line written when a stream fails partway through.
+#[derive(Serialize)]
+struct StreamErrorLine {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<Value>,
+    error: String,
+    code: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+/// The terminal NDJSON line written once a stream is exhausted.
+#[derive(Serialize)]
+struct StreamDoneLine {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<Value>,
+    done: bool,
+}
+
+impl RaycastExecutor {
+    /// Look up a `#[raycast(stream)]` function and build its stream of
+    /// results, without driving it. Use [`RaycastExecutor::drive_stream_to_stdout`]
+    /// to consume it.
+    pub fn execute_stream(
+        function_name: &str,
+        params: impl Into<Params>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Value, RaycastError>> + Send>>, RaycastError> {
+        let function = inventory::iter::<RaycastFunction>()
+            .find(|f| f.name == function_name)
+            .ok_or_else(|| RaycastError::FunctionNotFound {
+                function: function_name.to_string(),
+            })?;
+
+        let execute_stream = function.execute_stream.ok_or_else(|| RaycastError::ExecutionError {
+            error: format!("'{}' is not a streaming function", function_name),
+        })?;
+
+        Ok(execute_stream(params.into()))
+    }
+
+    /// Whether `function_name` names a `#[raycast(stream)]` function.
+    pub fn is_stream_function(function_name: &str) -> bool {
+        inventory::iter::<RaycastFunction>()
+            .find(|f| f.name == function_name)
+            .is_some_and(|f| f.is_stream)
+    }
+
+    /// Drive a stream to completion, writing each item as its own NDJSON
+    /// line, followed by a terminal `{"done":true}` marker. `id` carries the
+    /// originating JSON-RPC request id through every line when called from
+    /// [`RaycastExecutor::serve_stdio`]; it's `None` for the plain CLI path.
+    ///
+    /// An `Err` item ends the stream: one `StreamErrorLine` is written and
+    /// the underlying stream is dropped without polling it further, the same
+    /// way a `?` on a `Result`-returning iterator would stop rather than
+    /// continuing past a failure.
+    pub async fn drive_stream_to_stdout(
+        mut stream: Pin<Box<dyn Stream<Item = Result<Value, RaycastError>> + Send>>,
+        id: Option<Value>,
+    ) -> Result<(), RaycastError> {
+        let mut stdout = std::io::stdout();
+
+        while let Some(item) = stream.next().await {
+            match item {
+                Ok(value) => {
+                    let line = serde_json::to_string(&StreamItemLine {
+                        id: id.clone(),
+                        item: value,
+                    })
+                    .map_err(|e| RaycastError::JsonError {
+                        error: format!("Failed to serialize stream item: {}", e),
+                    })?;
+                    writeln!(stdout, "{}", line).map_err(|e| RaycastError::JsonError {
+                        error: format!("Failed to write to stdout: {}", e),
+                    })?;
+                }
+                Err(e) => {
+                    let (code, data) = e.code_and_data();
+                    let line = serde_json::to_string(&StreamErrorLine {
+                        id: id.clone(),
+                        error: e.to_string(),
+                        code,
+                        data,
+                    })
+                    .map_err(|e| RaycastError::JsonError {
+                        error: format!("Failed to serialize stream item: {}", e),
+                    })?;
+                    writeln!(stdout, "{}", line).map_err(|e| RaycastError::JsonError {
+                        error: format!("Failed to write to stdout: {}", e),
+                    })?;
+                    break;
+                }
+            }
+        }
+
+        let done_line = serde_json::to_string(&StreamDoneLine { id, done: true }).map_err(|e| RaycastError::JsonError {
+            error: format!("Failed to serialize stream terminator: {}", e),
+        })?;
+        writeln!(stdout, "{}", done_line).map_err(|e| RaycastError::JsonError {
+            error: format!("Failed to write to stdout: {}", e),
+        })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{RaycastExecutor, RaycastFunction, RaycastTypeInfo};
+
+impl RaycastExecutor {
+    /// Render `.d.ts` declarations for every `#[raycast]`-exported function,
+    /// by walking the same `inventory` registry used for dispatch. Intended
+    /// for the `--emit-types` CLI flag, so the TypeScript side of a Raycast
+    /// extension gets compile-time-checked bindings instead of hand-rolled
+    /// `Vec<Value>` calls.
+    pub fn emit_dts() -> String {
+        let mut out = String::from(
+            "// Auto-generated by raycast-rust-runtime --emit-types. Do not edit by hand.\n\n",
+        );
+
+        let type_decls = Self::emit_type_declarations();
+        if !type_decls.is_empty() {
+            out.push_str(&type_decls);
+            out.push('\n');
+        }
+
+        for function in inventory::iter::<RaycastFunction>() {
+            let params = function
+                .params
+                .iter()
+                .map(|(name, ty)| format!("{}: {}", name, rust_type_to_ts(ty)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let returns = rust_type_to_ts(function.returns);
+
+            if function.is_stream {
+                out.push_str(&format!(
+                    "export declare function {}({}): AsyncIterable<{}>;\n",
+                    function.name, params, returns
+                ));
+            } else {
+                out.push_str(&format!(
+                    "export declare function {}({}): Promise<{}>;\n",
+                    function.name, params, returns
+                ));
+            }
+        }
+
+        out
+    }
+
+    /// Render an `interface` for every struct type reachable from a
+    /// registered function's params/return type (transitively, through
+    /// fields of other `#[raycast_type]` structs) that has a matching
+    /// `#[raycast_type]` registration. A reachable struct with no
+    /// registration (the author forgot `#[raycast_type]`, or it's a foreign
+    /// type) falls back to an `export type X = unknown;` stub, so the output
+    /// still typechecks standalone instead of referencing an undeclared name.
+    fn emit_type_declarations() -> String {
+        let mut pending: Vec<String> = Self::collect_opaque_types().into_iter().collect();
+        let mut visited = BTreeSet::new();
+        let mut declared = BTreeMap::new();
+
+        while let Some(name) = pending.pop() {
+            if !visited.insert(name.clone()) {
+                continue;
+            }
+
+            match inventory::iter::<RaycastTypeInfo>().find(|info| info.name == name) {
+                Some(info) => {
+                    let fields = info
+                        .fields
+                        .iter()
+                        .map(|(field_name, ty)| {
+                            collect_opaque_type(ty, &mut pending);
+                            format!("{}: {};", field_name, rust_type_to_ts(ty))
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    declared.insert(name.clone(), format!("export interface {} {{ {} }}\n", name, fields));
+                }
+                None => {
+                    declared.insert(name.clone(), format!("export type {} = unknown;\n", name));
+                }
+            }
+        }
+
+        declared.into_values().collect()
+    }
+
+    /// Every referenced-but-undeclared type name across all registered
+    /// functions' params and return types.
+    fn collect_opaque_types() -> BTreeSet<String> {
+        let mut opaque_types = BTreeSet::new();
+
+        for function in inventory::iter::<RaycastFunction>() {
+            for (_, ty) in function.params {
+                collect_opaque_type_into_set(ty, &mut opaque_types);
+            }
+            collect_opaque_type_into_set(function.returns, &mut opaque_types);
+        }
+
+        opaque_types
+    }
+}
+
+/// Walk through `Vec<_>`/`Option<_>` wrappers and record the innermost type
+/// name if it isn't one `rust_type_to_ts` already maps to a TS primitive.
+fn collect_opaque_type_into_set(ty: &str, out: &mut BTreeSet<String>) {
+    if let Some(inner) = strip_generic(ty, "Vec").or_else(|| strip_generic(ty, "Option")) {
+        return collect_opaque_type_into_set(inner, out);
+    }
+
+    if !is_known_primitive(ty) {
+        out.insert(ty.to_string());
+    }
+}
+
+/// Same as [`collect_opaque_type_into_set`], but appending to the work queue
+/// driving [`RaycastExecutor::emit_type_declarations`] instead of a set.
+fn collect_opaque_type(ty: &str, pending: &mut Vec<String>) {
+    if let Some(inner) = strip_generic(ty, "Vec").or_else(|| strip_generic(ty, "Option")) {
+        return collect_opaque_type(inner, pending);
+    }
+
+    if !is_known_primitive(ty) {
+        pending.push(ty.to_string());
+    }
+}
+
+/// Whether `rust_type_to_ts` maps `ty` to a TS primitive rather than passing
+/// it through as an assumed struct name.
+fn is_known_primitive(ty: &str) -> bool {
+    matches!(
+        ty,
+        "String" | "str" | "&str" | "char" | "bool" | "()"
+            | "f32" | "f64" | "i8" | "i16" | "i32" | "i64" | "i128" | "isize"
+            | "u8" | "u16" | "u32" | "u64" | "u128" | "usize"
+    )
+}
+
+/// Map a Rust type's source text (as captured by the `#[raycast]` macro) to
+/// the closest TypeScript type. Unrecognized names are passed through
+/// unchanged, on the assumption that `emit_type_declarations` has (or will)
+/// declare a same-named `interface`/stub for them.
+fn rust_type_to_ts(ty: &str) -> String {
+    if let Some(inner) = strip_generic(ty, "Vec") {
+        let inner_ts = rust_type_to_ts(inner);
+        if inner_ts.contains(" | ") {
+            return format!("({})[]", inner_ts);
+        }
+        return format!("{}[]", inner_ts);
+    }
+
+    if let Some(inner) = strip_generic(ty, "Option") {
+        return format!("{} | null", rust_type_to_ts(inner));
+    }
+
+    match ty {
+        "String" | "str" | "&str" | "char" => "string".to_string(),
+        "bool" => "boolean".to_string(),
+        "f32" | "f64" | "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16"
+        | "u32" | "u64" | "u128" | "usize" => "number".to_string(),
+        "()" => "void".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// If `ty` is `Wrapper<Inner>`, return `Inner`'s source text.
+fn strip_generic<'a>(ty: &'a str, wrapper: &str) -> Option<&'a str> {
+    let prefix = format!("{}<", wrapper);
+    ty.strip_prefix(&prefix)?.strip_suffix('>')
+}